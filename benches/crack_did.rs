@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use memmem::{Searcher, TwoWaySearcher};
+use regex::Regex;
+
+use vanity_did_plc::cracker::crack_iteration;
+use vanity_did_plc::insecure_ecdsa::{generate_ecdsa_constants, SECP256K1};
+use vanity_did_plc::plc_op::{Service, SignedCreateOp, UnsignedCreateOp};
+
+fn find_needle(buf: &[u8], marker: u8, length: usize) -> usize {
+    let needle = vec![marker; length];
+    let searcher = TwoWaySearcher::new(&needle);
+    searcher.search_in(buf).expect("couldn't find needle")
+}
+
+fn bench_crack_iteration(c: &mut Criterion) {
+    let constants = generate_ecdsa_constants(SECP256K1);
+    // matches nothing, so every candidate pays the full patch + signatures + sha256 + base32
+    // + regex cost without short-circuiting on an early match
+    let regex = Regex::new("^$a").unwrap();
+
+    let op = UnsignedCreateOp {
+        ty: "plc_operation".to_string(),
+        verification_methods: HashMap::new(),
+        rotation_keys: vec!["did:key:zQ3shVc2UkAfJCdc1TR8E66J85h48P43r93q8jGPkPpjF9Ef9".to_string()],
+        also_known_as: vec![],
+        services: HashMap::from([(
+            "did_prefix".to_string(), Service {
+                ty: ":3_0".to_string(),
+                endpoint: "00000000000000000000000000000000".to_string(),
+            }
+        )]),
+        prev: None,
+    };
+
+    let unsigned_buf = serde_ipld_dagcbor::to_vec(&op).expect("cbor encoding failed");
+
+    let signed_op = SignedCreateOp { op, sig: "\x01".repeat(86) };
+    let signed_buf = serde_ipld_dagcbor::to_vec(&signed_op).expect("cbor encoding failed");
+    let signed_sig_index = find_needle(&signed_buf, 1u8, 86);
+
+    let mut group = c.benchmark_group("crack_did");
+    group.throughput(Throughput::Elements(constants.len() as u64));
+    group.bench_function("crack_iteration", |b| {
+        b.iter_batched(
+            || signed_buf.clone(),
+            |mut signed_buf| {
+                crack_iteration(
+                    black_box(&constants), black_box(SECP256K1), black_box(&unsigned_buf),
+                    black_box(&mut signed_buf), black_box(signed_sig_index), black_box(&regex),
+                )
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_crack_iteration);
+criterion_main!(benches);
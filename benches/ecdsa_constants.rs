@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use vanity_did_plc::insecure_ecdsa::{generate_ecdsa_constants, SECP256K1};
+
+fn bench_generate_ecdsa_constants(c: &mut Criterion) {
+    c.bench_function("generate_ecdsa_constants (startup)", |b| {
+        b.iter(|| generate_ecdsa_constants(SECP256K1))
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_generate_ecdsa_constants
+}
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ethnum::U256;
+
+use vanity_did_plc::insecure_ecdsa::SECP256K1;
+use vanity_did_plc::math::{mod_inverse, mul_mod};
+
+fn bench_mul_mod(c: &mut Criterion) {
+    let a = SECP256K1.g.0;
+    let b = SECP256K1.n - U256::ONE;
+
+    c.bench_function("mul_mod", |bencher| {
+        bencher.iter(|| mul_mod(black_box(a), black_box(b), black_box(SECP256K1.p)))
+    });
+}
+
+fn bench_mod_inverse(c: &mut Criterion) {
+    let a = SECP256K1.g.0;
+
+    c.bench_function("mod_inverse", |bencher| {
+        bencher.iter(|| mod_inverse(black_box(a), black_box(SECP256K1.p)))
+    });
+}
+
+fn bench_scalar_multiply(c: &mut Criterion) {
+    let k = SECP256K1.n - U256::new(12345u128);
+
+    c.bench_function("scalar_multiply", |bencher| {
+        bencher.iter(|| SECP256K1.scalar_multiply(black_box(k), black_box(SECP256K1.g)))
+    });
+}
+
+criterion_group!(benches, bench_mul_mod, bench_mod_inverse, bench_scalar_multiply);
+criterion_main!(benches);
@@ -0,0 +1,18 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use vanity_did_plc::insecure_ecdsa::{generate_ecdsa_constants, generate_signatures, SECP256K1};
+
+fn bench_generate_signatures(c: &mut Criterion) {
+    let constants = generate_ecdsa_constants(SECP256K1);
+    let buf = vec![0u8; 256];
+
+    let mut group = c.benchmark_group("signatures");
+    group.throughput(Throughput::Elements(constants.len() as u64));
+    group.bench_function("generate_signatures", |b| {
+        b.iter(|| generate_signatures(black_box(&buf), black_box(&constants), black_box(SECP256K1)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate_signatures);
+criterion_main!(benches);
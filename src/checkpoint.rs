@@ -0,0 +1,30 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// The seed and per-thread `i` counters needed to resume an interrupted run from where it
+/// stopped, instead of re-scanning from the start.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub seed: u64,
+    pub thread_counters: Vec<u128>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let data = fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let data = serde_json::to_string(self).expect("checkpoint serialization failed");
+
+        // Write to a sibling temp file and rename it into place - rename is atomic on the same
+        // filesystem, so a crash can never land on a half-written checkpoint.
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(&tmp_path, path)
+    }
+}
@@ -0,0 +1,48 @@
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::insecure_ecdsa::{ConstantTableEntry, generate_signatures};
+use crate::math::Curve;
+
+/// Tries every candidate signature for one value of `i`, returning the signature and `did:plc:`
+/// string for each one whose resulting DID matches `regex`. This is the hot loop `crack_did`
+/// repeatedly drives, pulled out so it can be measured in isolation by the benches.
+///
+/// `unsigned_buf` must already have its `i` field patched in; `signed_buf` must already have
+/// both its `i` field patched in and be the same buffer `signed_sig_index` was found in - this
+/// function patches only the signature bytes as it tries each candidate.
+pub fn crack_iteration(
+    constants: &[ConstantTableEntry], curve: Curve,
+    unsigned_buf: &[u8], signed_buf: &mut [u8], signed_sig_index: usize, regex: &Regex,
+) -> Vec<(String, String)> {
+    let sigs = generate_signatures(unsigned_buf, constants, curve);
+
+    // every candidate signature only changes the 86 bytes at `signed_sig_index` - precompute a
+    // midstate covering every complete block strictly before it, so each candidate below only
+    // has to hash its own trailing blocks instead of the whole buffer.
+    let prefix_len = (signed_sig_index / 64) * 64;
+    let base_hasher = {
+        let mut hasher = Sha256::new();
+        hasher.update(&signed_buf[..prefix_len]);
+        hasher
+    };
+
+    let mut matches = Vec::new();
+
+    for sig in sigs {
+        signed_buf[signed_sig_index..(signed_sig_index + 86)].copy_from_slice(sig.as_bytes());
+
+        // hash the signed operation to generate the DID, resuming from the cached midstate
+        let mut hasher = base_hasher.clone();
+        hasher.update(&signed_buf[prefix_len..]);
+        let hash = hasher.finalize();
+        let mut digest = base32::encode(base32::Alphabet::Rfc4648Lower { padding: false }, hash.as_slice());
+        digest.truncate(24);
+
+        if regex.is_match(&digest) {
+            matches.push((sig, format!("did:plc:{digest}")));
+        }
+    }
+
+    matches
+}
@@ -15,6 +15,46 @@ pub const SECP256K1: Curve = Curve {
     n: U256::from_words(0xfffffffffffffffffffffffffffffffeu128, 0xbaaedce6af48a03bbfd25e8cd0364141u128),
 };
 
+/// The secp256k1 multicodec prefix used to build a `did:key` from a compressed public key.
+pub const SECP256K1_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+
+pub const P256: Curve = Curve {
+    a: U256::from_words(0xffffffff000000010000000000000000u128, 0x00000000fffffffffffffffffffffffcu128),
+    b: U256::from_words(0x5ac635d8aa3a93e7b3ebbd55769886bcu128, 0x651d06b0cc53b0f63bce3c3e27d2604bu128),
+    p: U256::from_words(0xffffffff000000010000000000000000u128, 0x00000000ffffffffffffffffffffffffu128),
+    g: (
+        U256::from_words(0x6b17d1f2e12c4247f8bce6e563a440f2u128, 0x77037d812deb33a0f4a13945d898c296u128),
+        U256::from_words(0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e16u128, 0x2bce33576b315ececbb6406837bf51f5u128),
+    ),
+    n: U256::from_words(0xffffffff00000000ffffffffffffffffu128, 0xbce6faada7179e84f3b9cac2fc632551u128),
+};
+
+/// The P-256 multicodec prefix used to build a `did:key` from a compressed public key.
+pub const P256_MULTICODEC: [u8; 2] = [0x80, 0x24];
+
+/// Which curve to crack/sign under, selectable via `--curve`.
+#[derive(Debug, Copy, Clone, clap::ValueEnum)]
+pub enum CurveKind {
+    K256,
+    P256,
+}
+
+impl CurveKind {
+    pub fn curve(self) -> Curve {
+        match self {
+            CurveKind::K256 => SECP256K1,
+            CurveKind::P256 => P256,
+        }
+    }
+
+    pub fn multicodec(self) -> [u8; 2] {
+        match self {
+            CurveKind::K256 => SECP256K1_MULTICODEC,
+            CurveKind::P256 => P256_MULTICODEC,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct ConstantTableEntry {
     pub k_pow_neg1_times_r: U256,
@@ -29,10 +69,13 @@ pub struct ConstantTableEntry {
 pub fn generate_ecdsa_constants(curve: Curve) -> Vec<ConstantTableEntry> {
     let mut constants = Vec::with_capacity(256);
 
+    // `k^-1` is `2^i`, so `k = (2^i)^-1 = inv(2)^i` - compute `inv(2)` once and raise it
+    // iteratively instead of calling `mod_inverse` for every one of the 256 entries.
+    let inv2 = mod_inverse(U256::new(2u128), curve.n);
+    let mut k = U256::ONE;
+
     for i in 0..256 {
-    // let i = 253; {
         let k_pow_neg1 = U256::ONE << i; // the target value of k^-1
-        let k = mod_inverse(k_pow_neg1, curve.n); // computed value of k
 
         // compute the value of `r`
         let point = curve.scalar_multiply(k, curve.g);
@@ -43,6 +86,8 @@ pub fn generate_ecdsa_constants(curve: Curve) -> Vec<ConstantTableEntry> {
             k_pow_neg1_times_r,
             r,
         });
+
+        k = mul_mod(k, inv2, curve.n);
     }
 
     constants
@@ -0,0 +1,51 @@
+use ethnum::U256;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::math::Curve;
+
+/// Generates a cryptographically random scalar in `[1, curve.n)`, suitable for use as a private
+/// key.
+pub fn random_scalar(curve: &Curve) -> U256 {
+    loop {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let candidate = U256::from_be_bytes(bytes) % curve.n;
+
+        if candidate != 0 {
+            return candidate;
+        }
+    }
+}
+
+/// Deterministically derives a private scalar from a passphrase ("brain wallet"), by hashing the
+/// phrase with SHA-256 and reducing the result modulo the curve order. The phrase should have
+/// enough entropy to resist brute-forcing, same as any brain wallet scheme.
+pub fn brain_wallet_scalar(phrase: &str, curve: &Curve) -> U256 {
+    let hash = Sha256::digest(phrase.as_bytes());
+    let scalar = U256::from_be_bytes(hash.as_slice().try_into().unwrap()) % curve.n;
+
+    assert_ne!(scalar, 0, "brain wallet phrase hashed to an invalid (zero) private key");
+    scalar
+}
+
+/// Encodes a public key point as a `did:key` string: the compressed point, prefixed with the
+/// curve's multicodec varint, multibase-encoded as base58btc (leading `z`).
+pub fn encode_did_key(multicodec: [u8; 2], public_key: (U256, U256)) -> String {
+    let (x, y) = public_key;
+
+    let mut compressed = Vec::with_capacity(multicodec.len() + 33);
+    compressed.extend_from_slice(&multicodec);
+    compressed.push(if (y & 1) == 1 { 0x03 } else { 0x02 });
+    compressed.extend_from_slice(&x.to_be_bytes());
+
+    format!("did:key:z{}", bs58::encode(compressed).into_string())
+}
+
+/// Generates a fresh rotation keypair, returning its `did:key` and private scalar.
+pub fn generate_keypair(curve: &Curve, multicodec: [u8; 2]) -> (String, U256) {
+    let scalar = random_scalar(curve);
+    let public_key = curve.scalar_multiply(scalar, curve.g);
+
+    (encode_did_key(multicodec, public_key), scalar)
+}
@@ -0,0 +1,7 @@
+pub mod checkpoint;
+pub mod cracker;
+pub mod insecure_ecdsa;
+pub mod keygen;
+pub mod math;
+pub mod output;
+pub mod plc_op;
@@ -2,6 +2,7 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::exit;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -13,17 +14,16 @@ use memmem::{Searcher, TwoWaySearcher};
 use parking_lot::Mutex;
 use rand::random;
 use regex::Regex;
-use sha2::{Digest, Sha256};
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 
-use crate::insecure_ecdsa::{ConstantTableEntry, generate_ecdsa_constants, generate_signatures, SECP256K1};
-use crate::math::Curve;
-use crate::plc_op::{Service, SignedCreateOp, UnsignedCreateOp};
-
-mod plc_op;
-mod insecure_ecdsa;
-mod math;
+use vanity_did_plc::checkpoint::Checkpoint;
+use vanity_did_plc::cracker::crack_iteration;
+use vanity_did_plc::insecure_ecdsa::{ConstantTableEntry, CurveKind, generate_ecdsa_constants};
+use vanity_did_plc::keygen::{brain_wallet_scalar, encode_did_key, generate_keypair};
+use vanity_did_plc::math::Curve;
+use vanity_did_plc::output::{OutputMode, OutputWriter};
+use vanity_did_plc::plc_op::{Service, SignedCreateOp, UnsignedCreateOp};
 
 #[derive(Debug)]
 struct Metrics {
@@ -52,21 +52,62 @@ struct Args {
     #[arg(long, default_value_t = Cow::Borrowed("https://plc.directory"))]
     plc_directory: Cow<'static, str>,
 
-    /// The (secure) rotation key to register on created DIDs.
+    /// Which curve to sign the genesis operation's insecure key-of-1 under (and, when
+    /// generating or deriving a rotation keypair, to generate it under too).
+    #[arg(long, value_enum, default_value_t = CurveKind::K256)]
+    curve: CurveKind,
+
+    /// The (secure) rotation key to register on created DIDs, as a `did:key` string.
     ///
     /// An additional key will be added after this key, with a private key of 1 - this key is
-    /// inherently insecure and should be removed as soon as possible. It has the DID
-    /// `did:key:zQ3shVc2UkAfJCdc1TR8E66J85h48P43r93q8jGPkPpjF9Ef9`.
-    rotation_key: String,
+    /// inherently insecure and should be removed as soon as possible. Its `did:key` is printed
+    /// at startup.
+    ///
+    /// Exactly one of `--rotation-key`, `--generate-key` or `--brain` must be given.
+    #[arg(long, value_name = "DID_KEY")]
+    rotation_key: Option<String>,
+
+    /// Generate a fresh rotation keypair instead of supplying `rotation_key`, printing the
+    /// private key alongside its `did:key` so it can be captured before the insecure key-of-1
+    /// is revoked.
+    #[arg(long)]
+    generate_key: bool,
+
+    /// Derive the rotation keypair deterministically from a passphrase ("brain wallet"),
+    /// instead of supplying `rotation_key`. Like `--generate-key`, this prints the derived
+    /// private key alongside its `did:key`.
+    #[arg(long, value_name = "PHRASE")]
+    brain: Option<String>,
+
+    /// Append every found DID to this file as JSONL, decoupling discovery from submission -
+    /// the recorded signed operations can be POSTed to a PLC directory at any later time.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// What to record per found DID when `--output` is set: the full signed operation, just
+    /// the `did:plc:` string, or just the signature.
+    #[arg(long, value_enum, default_value_t = OutputMode::Full)]
+    output_mode: OutputMode,
+
+    /// Periodically persist per-thread progress (and the seed) to this file, so an interrupted
+    /// multi-hour search can continue with `--resume` instead of re-scanning from the start.
+    #[arg(long, value_name = "PATH")]
+    checkpoint: Option<PathBuf>,
+
+    /// Reload the seed and per-thread progress from `--checkpoint` instead of starting fresh.
+    #[arg(long)]
+    resume: bool,
 
     /// The regex to match created DIDs against. This doesn't include the `did:plc:` prefix.
     regex: String,
 }
 
-/// This key has a private key of 1 - it's used for fast signature generation. The security
-/// doesn't really matter as this key is used solely for signing the genesis operation, and is
-/// then immediately revoked by the primary rotation key defined above.
-const INSECURE_ROTATION_KEY: &'static str = "did:key:zQ3shVc2UkAfJCdc1TR8E66J85h48P43r93q8jGPkPpjF9Ef9";
+/// Builds the `did:key` of the curve's key-of-1 - it's used for fast signature generation. The
+/// security doesn't really matter as this key is used solely for signing the genesis operation,
+/// and is then immediately revoked by the primary rotation key defined above.
+fn insecure_rotation_key(curve: Curve, multicodec: [u8; 2]) -> String {
+    encode_did_key(multicodec, curve.g)
+}
 
 /// After how many iterations of the DID loop to do before updating the metrics.
 const METRIC_UPDATE_INTERVAL: u128 = 1000;
@@ -82,12 +123,12 @@ fn crack_did(
     constants: Vec<ConstantTableEntry>, curve: Curve,
     mut unsigned_buf: Vec<u8>, unsigned_i_index: usize,
     mut signed_buf: Vec<u8>, signed_i_index: usize, signed_sig_index: usize,
-    mut signed_op: SignedCreateOp, thread_idx: u64, shutdown_flag: Arc<AtomicBool>,
+    mut signed_op: SignedCreateOp, thread_idx: u64, start_i: u128, shutdown_flag: Arc<AtomicBool>,
     output_channel: mpsc::UnboundedSender<(SignedCreateOp, String)>, regex: Regex,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Mutex<Metrics>>, progress: Arc<Mutex<Vec<u128>>>,
 ) {
-    let mut i = 0;
-    let mut last_metrics_i = 0;
+    let mut i = start_i;
+    let mut last_metrics_i = start_i;
 
     while shutdown_flag.load(Ordering::Relaxed) {
         // generate a hex value for `i` and patch it into both buffers
@@ -95,28 +136,16 @@ fn crack_did(
         unsigned_buf[unsigned_i_index..(unsigned_i_index + 32)].copy_from_slice(&i_hex.as_bytes());
         signed_buf[signed_i_index..(signed_i_index + 32)].copy_from_slice(&i_hex.as_bytes());
 
-        // generate many valid signatures for the operation
-        let sigs = generate_signatures(&unsigned_buf, &constants, curve);
-
-        for sig in sigs {
-            // patch the signature into the buffer
-            signed_buf[signed_sig_index..(signed_sig_index + 86)].copy_from_slice(sig.as_bytes());
+        // try every candidate signature for this `i`
+        let matches = crack_iteration(&constants, curve, &unsigned_buf, &mut signed_buf, signed_sig_index, &regex);
 
-            // hash the signed operation to generate the DID
-            let hash = Sha256::digest(&signed_buf);
-            let mut digest = base32::encode(base32::Alphabet::Rfc4648Lower { padding: false }, hash.as_slice());
-            digest.truncate(24);
+        for (sig, did) in matches {
+            // patch the values we used back into the struct for JSON serialization
+            signed_op.op.services.get_mut("did_prefix").unwrap().endpoint = i_hex.clone();
+            signed_op.sig = sig;
 
-            if regex.is_match(&digest) {
-                // patch the values we used back into the struct for JSON serialization
-                signed_op.op.services.get_mut("did_prefix").unwrap().endpoint = i_hex.clone();
-                signed_op.sig = sig;
-
-                let did = format!("did:plc:{}", &digest);
-
-                if output_channel.send((signed_op.clone(), did)).is_err() {
-                    return;
-                }
+            if output_channel.send((signed_op.clone(), did)).is_err() {
+                return;
             }
         }
 
@@ -126,6 +155,8 @@ fn crack_did(
             let mut metrics_guard = metrics.lock();
             metrics_guard.total_checked += (i - last_metrics_i) * constants.len() as u128;
             last_metrics_i = i;
+
+            progress.lock()[thread_idx as usize] = i;
         }
     }
 }
@@ -134,10 +165,34 @@ fn crack_did(
 async fn main() {
     let args: Args = Args::parse();
 
-    let seed = if args.seed == 0 {
-        random()
+    let cracker_threads = if args.worker_threads == 0 {
+        num_cpus::get()
     } else {
-        args.seed
+        args.worker_threads
+    };
+
+    let (seed, thread_start_counters) = if args.resume {
+        let checkpoint_path = args.checkpoint.clone().unwrap_or_else(|| {
+            eprintln!("`--resume` requires `--checkpoint <path>`");
+            exit(1);
+        });
+
+        let mut checkpoint = Checkpoint::load(&checkpoint_path).unwrap_or_else(|err| {
+            eprintln!("failed to load checkpoint at {}: {err}", checkpoint_path.display());
+            exit(1);
+        });
+
+        eprintln!("resuming from checkpoint at {}", checkpoint_path.display());
+        checkpoint.thread_counters.resize(cracker_threads, 0);
+        (checkpoint.seed, checkpoint.thread_counters)
+    } else {
+        let seed = if args.seed == 0 {
+            random()
+        } else {
+            args.seed
+        };
+
+        (seed, vec![0u128; cracker_threads])
     };
 
     let regex = match Regex::new(&args.regex) {
@@ -158,6 +213,31 @@ async fn main() {
         total_checked: 0,
     }));
 
+    let curve = args.curve.curve();
+    let multicodec = args.curve.multicodec();
+
+    let rotation_key = match (&args.rotation_key, args.generate_key, &args.brain) {
+        (Some(key), false, None) => key.clone(),
+        (None, true, None) => {
+            let (did_key, private_key) = generate_keypair(&curve, multicodec);
+            eprintln!("generated rotation private key: {private_key:064x}");
+            eprintln!("generated rotation did:key: {did_key}");
+            did_key
+        }
+        (None, false, Some(phrase)) => {
+            let scalar = brain_wallet_scalar(phrase, &curve);
+            let public_key = curve.scalar_multiply(scalar, curve.g);
+            let did_key = encode_did_key(multicodec, public_key);
+            eprintln!("derived rotation private key: {scalar:064x}");
+            eprintln!("derived rotation did:key: {did_key}");
+            did_key
+        }
+        _ => {
+            eprintln!("specify exactly one of: `--rotation-key <did:key>`, `--generate-key`, or `--brain <phrase>`");
+            exit(1);
+        }
+    };
+
     eprintln!("using initial seed: {seed}");
     eprintln!("matching against: {}", args.regex);
 
@@ -167,10 +247,12 @@ async fn main() {
         eprintln!("submitting DIDs to {}", args.plc_directory);
     }
 
+    let insecure_rotation_key = insecure_rotation_key(curve, multicodec);
+    eprintln!("insecure key-of-1 did:key (revoke this immediately after account migration): {insecure_rotation_key}");
+
     // create ECDSA constants
     eprintln!("generating ECDSA constants...");
     let time = Instant::now();
-    let curve = SECP256K1;
     let constants = generate_ecdsa_constants(curve);
     let time_taken = Instant::now() - time;
     eprintln!("generated ECDSA constants in {:.3}s", time_taken.as_secs_f64());
@@ -179,7 +261,7 @@ async fn main() {
     let op = UnsignedCreateOp {
         ty: "plc_operation".to_string(),
         verification_methods: HashMap::new(),
-        rotation_keys: vec![args.rotation_key.to_string(), INSECURE_ROTATION_KEY.to_string()],
+        rotation_keys: vec![rotation_key, insecure_rotation_key],
         also_known_as: vec![],
         services: HashMap::from([(
             "did_prefix".to_string(), Service {
@@ -204,17 +286,12 @@ async fn main() {
 
     // spawn worker threads
     let running_flag = Arc::new(AtomicBool::new(true));
-
-    let cracker_threads = if args.worker_threads == 0 {
-        num_cpus::get()
-    } else {
-        args.worker_threads
-    };
+    let progress = Arc::new(Mutex::new(thread_start_counters.clone()));
 
     let (output_channel_tx, mut output_channel_rx) = mpsc::unbounded_channel();
     let mut cracker_handles = Vec::new();
 
-    for thread_idx in 0..cracker_threads {
+    for (thread_idx, &start_i) in thread_start_counters.iter().enumerate() {
         let thread_handle = thread::spawn({
             let constants = constants.clone();
             let cbor_buf = cbor_buf.clone();
@@ -224,12 +301,13 @@ async fn main() {
             let output_channel_tx = output_channel_tx.clone();
             let regex = regex.clone();
             let metrics = metrics.clone();
+            let progress = progress.clone();
 
             move || {
                 crack_did(
                     constants, curve, cbor_buf, unsigned_i_index, signed_cbor_buf, signed_i_index,
-                    signed_sig_index, signed_op, thread_idx as u64, running_flag, output_channel_tx,
-                    regex, metrics
+                    signed_sig_index, signed_op, thread_idx as u64, start_i, running_flag,
+                    output_channel_tx, regex, metrics, progress
                 )
             }
         });
@@ -237,12 +315,41 @@ async fn main() {
         cracker_handles.push(thread_handle);
     }
 
+    if let Some(checkpoint_path) = args.checkpoint.clone() {
+        let progress = progress.clone();
+
+        tokio::spawn(async move {
+            loop {
+                sleep(Duration::from_secs(10)).await;
+
+                let checkpoint = Checkpoint { seed, thread_counters: progress.lock().clone() };
+
+                if let Err(err) = checkpoint.save(&checkpoint_path) {
+                    eprintln!("failed to save checkpoint: {err}");
+                }
+            }
+        });
+    }
+
+    let mut output_writer = args.output.as_ref().map(|path| {
+        OutputWriter::open(path, args.output_mode).unwrap_or_else(|err| {
+            eprintln!("failed to open output file {}: {err}", path.display());
+            exit(1);
+        })
+    });
+
     tokio::spawn(async move {
         let client = reqwest::Client::new();
 
         loop {
             let Some((signed_op, did)) = output_channel_rx.recv().await else { return };
 
+            if let Some(writer) = output_writer.as_mut() {
+                if let Err(err) = writer.write_record(&signed_op, &did) {
+                    eprintln!("failed to write output record for {did}: {err}");
+                }
+            }
+
             if !args.dry_run {
                 let res = client.post(format!("{}/{}", &args.plc_directory, &did))
                     .json(&signed_op)
@@ -286,5 +393,13 @@ async fn main() {
         let _ = cracker_handle.join();
     }
 
+    if let Some(checkpoint_path) = &args.checkpoint {
+        let checkpoint = Checkpoint { seed, thread_counters: progress.lock().clone() };
+
+        if let Err(err) = checkpoint.save(checkpoint_path) {
+            eprintln!("failed to save checkpoint: {err}");
+        }
+    }
+
     eprintln!("goodbye!");
 }
@@ -91,11 +91,16 @@ pub fn mod_inverse(mut a: U256, mut b: U256) -> U256 {
 
 pub type Point = (U256, U256);
 
+// a point in Jacobian projective coordinates, representing the affine point
+// `(X / Z^2, Y / Z^3)`. Used internally so point addition/doubling never needs a modular
+// inverse - we only convert back to affine once, at the end of a scalar multiply.
+type JacobianPoint = (U256, U256, U256);
+
 #[derive(Debug, Copy, Clone)]
-// we don't use the `b` curve parameter, but we might as well include the full curve constants
-#[allow(dead_code)]
 pub struct Curve {
     pub a: U256,
+    // we don't use the `b` curve parameter, but we might as well include the full curve constants
+    #[allow(dead_code)]
     pub b: U256,
     pub p: U256,
     pub g: Point,
@@ -103,64 +108,99 @@ pub struct Curve {
 }
 
 impl Curve {
-    pub fn add_points(&self, p1: Point, p2: Point) -> Point {
-        let (x1, y1) = p1;
-        let (x2, y2) = p2;
-
-        let m = if x1 == x2 {
-            assert!(y1 == y2 && y1 != 0);
-
-            // a = (3 * x1 * x1 + self.a)
-            let a = add_mod(
-                mul_mod(
-                    mul_mod(x1, x1, self.p),
-                    U256::new(3u128), self.p
-                ),
-                self.a,
-                self.p
-            );
-
-            // b = pow(2 * y1, -1, self.p)
-            let b = mod_inverse(mul_mod(U256::new(2u128), y1, self.p), self.p);
-
-            // m = (a * b) % self.p
-            mul_mod(a, b, self.p)
-        } else {
-            // a = (y2 - y1)
-            let a = sub_mod(y2, y1, self.p);
+    fn to_jacobian(self, p: Point) -> JacobianPoint {
+        (p.0, p.1, U256::ONE)
+    }
+
+    fn to_affine(self, p: JacobianPoint) -> Point {
+        let (x, y, z) = p;
 
-            // b = pow(x2 - x1, -1, self.p)
-            let b = mod_inverse(sub_mod(x2, x1, self.p), self.p);
+        let z_inv = mod_inverse(z, self.p);
+        let z_inv2 = mul_mod(z_inv, z_inv, self.p);
+        let z_inv3 = mul_mod(z_inv2, z_inv, self.p);
+
+        (mul_mod(x, z_inv2, self.p), mul_mod(y, z_inv3, self.p))
+    }
+
+    fn double_jacobian(&self, p: JacobianPoint) -> JacobianPoint {
+        let (x, y, z) = p;
+
+        let y2 = mul_mod(y, y, self.p);
+        let s = mul_mod(mul_mod(U256::new(4u128), x, self.p), y2, self.p);
+
+        // m = 3 * x^2 + a * z^4 (the `a * z^4` term vanishes for secp256k1, where `a == 0`)
+        let z2 = mul_mod(z, z, self.p);
+        let z4 = mul_mod(z2, z2, self.p);
+        let m = add_mod(
+            mul_mod(U256::new(3u128), mul_mod(x, x, self.p), self.p),
+            mul_mod(self.a, z4, self.p),
+            self.p,
+        );
+
+        let x3 = sub_mod(mul_mod(m, m, self.p), mul_mod(U256::new(2u128), s, self.p), self.p);
+        let y2_sq = mul_mod(y2, y2, self.p);
+        let y3 = sub_mod(
+            mul_mod(m, sub_mod(s, x3, self.p), self.p),
+            mul_mod(U256::new(8u128), y2_sq, self.p),
+            self.p,
+        );
+        let z3 = mul_mod(mul_mod(U256::new(2u128), y, self.p), z, self.p);
+
+        (x3, y3, z3)
+    }
 
-            // m = (a * b) % self.p
-            mul_mod(a, b, self.p)
-        };
+    fn add_jacobian(&self, p1: JacobianPoint, p2: JacobianPoint) -> JacobianPoint {
+        let (x1, y1, z1) = p1;
+        let (x2, y2, z2) = p2;
 
-        // x3 = (m * m - x1 - x2) % self.p
-        let x3 = sub_mod(sub_mod(mul_mod(m, m, self.p), x1, self.p), x2, self.p);
+        let z1z1 = mul_mod(z1, z1, self.p);
+        let z2z2 = mul_mod(z2, z2, self.p);
 
-        // y3 = (m * (x1 - x3) - y1) % self.p
-        let y3 = sub_mod(mul_mod(m, sub_mod(x1, x3, self.p), self.p), y1, self.p);
+        let u1 = mul_mod(x1, z2z2, self.p);
+        let u2 = mul_mod(x2, z1z1, self.p);
+        let s1 = mul_mod(y1, mul_mod(z2, z2z2, self.p), self.p);
+        let s2 = mul_mod(y2, mul_mod(z1, z1z1, self.p), self.p);
 
-        (x3, y3)
+        if u1 == u2 {
+            assert!(s1 == s2, "cannot add a point to its own inverse");
+            return self.double_jacobian(p1);
+        }
+
+        let h = sub_mod(u2, u1, self.p);
+        let r = sub_mod(s2, s1, self.p);
+
+        let h2 = mul_mod(h, h, self.p);
+        let h3 = mul_mod(h2, h, self.p);
+        let u1h2 = mul_mod(u1, h2, self.p);
+
+        let x3 = sub_mod(sub_mod(mul_mod(r, r, self.p), h3, self.p), mul_mod(U256::new(2u128), u1h2, self.p), self.p);
+        let y3 = sub_mod(mul_mod(r, sub_mod(u1h2, x3, self.p), self.p), mul_mod(s1, h3, self.p), self.p);
+        let z3 = mul_mod(mul_mod(z1, z2, self.p), h, self.p);
+
+        (x3, y3, z3)
+    }
+
+    pub fn add_points(&self, p1: Point, p2: Point) -> Point {
+        let sum = self.add_jacobian(self.to_jacobian(p1), self.to_jacobian(p2));
+        self.to_affine(sum)
     }
 
     pub fn scalar_multiply(&self, mut k: U256, point: Point) -> Point {
-        let mut addend = point;
-        let mut result = None;
+        let mut addend = self.to_jacobian(point);
+        let mut result: Option<JacobianPoint> = None;
 
         while k != 0 {
             if (k & 1) == 1 {
                 result = Some(match result {
                     None => addend,
-                    Some(p) => self.add_points(p, addend),
+                    Some(p) => self.add_jacobian(p, addend),
                 });
             }
 
-            addend = self.add_points(addend, addend);
+            addend = self.double_jacobian(addend);
             k >>= 1;
         }
 
-        result.unwrap()
+        self.to_affine(result.unwrap())
     }
 }
\ No newline at end of file
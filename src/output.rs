@@ -0,0 +1,42 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::plc_op::SignedCreateOp;
+
+/// What to persist per found DID when `--output` is set, modeled on `ethkey`'s selectable
+/// output modes (full op, DID only, secret only).
+#[derive(Debug, Copy, Clone, clap::ValueEnum)]
+pub enum OutputMode {
+    Full,
+    Did,
+    Secret,
+}
+
+/// Appends found DIDs to a JSONL file, one record per line, so discovery can be decoupled from
+/// submission - the recorded operation is already signed, and can be POSTed to a PLC directory
+/// at any later time.
+pub struct OutputWriter {
+    mode: OutputMode,
+    file: BufWriter<File>,
+}
+
+impl OutputWriter {
+    pub fn open(path: &Path, mode: OutputMode) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { mode, file: BufWriter::new(file) })
+    }
+
+    pub fn write_record(&mut self, signed_op: &SignedCreateOp, did: &str) -> io::Result<()> {
+        let record = match self.mode {
+            OutputMode::Full => json!({ "did": did, "op": signed_op }),
+            OutputMode::Did => json!({ "did": did }),
+            OutputMode::Secret => json!({ "sig": signed_op.sig }),
+        };
+
+        writeln!(self.file, "{record}")?;
+        self.file.flush()
+    }
+}